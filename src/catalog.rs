@@ -0,0 +1,32 @@
+//! Known products that can be picked to pre-fill a sale item
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::money::Money;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+    pub id: usize,
+    pub name: String,
+    pub default_price: Money,
+    pub default_tax_group_id: String,
+}
+
+impl std::fmt::Display for Product {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.default_price)
+    }
+}
+
+fn catalog_path() -> PathBuf {
+    PathBuf::from("catalog.json")
+}
+
+/// Load the product catalog, falling back to an empty catalog if the file
+/// is missing or can't be parsed.
+pub fn load() -> Vec<Product> {
+    std::fs::read_to_string(catalog_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
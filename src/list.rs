@@ -9,6 +9,7 @@ use crate::sale::Sale;
 pub enum Message {
     NewSale,
     SelectSale(usize),
+    ViewReport,
 }
 
 pub fn view(sales: &HashMap<usize, Sale>) -> Element<Message> {
@@ -31,7 +32,11 @@ pub fn view(sales: &HashMap<usize, Sale>) -> Element<Message> {
 
     container(
         column![
-            row![button("New sale").on_press(Message::NewSale)],
+            row![
+                button("New sale").on_press(Message::NewSale),
+                button("Report").on_press(Message::ViewReport),
+            ]
+            .spacing(8),
             scrollable(rows),
         ]
         .spacing(16)
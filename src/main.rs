@@ -6,8 +6,13 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 mod action;
+mod catalog;
 mod list;
+mod money;
+mod render;
+mod report;
 mod sale;
+mod store;
 mod tax;
 
 pub use action::Action;
@@ -27,18 +32,22 @@ fn main() -> iced::Result {
 enum Screen {
     List,
     Sale(sale::Mode, usize),
+    Report,
 }
 
 #[derive(Debug)]
 enum Message {
     List(list::Message),
     Sale(usize, sale::Message),
+    Report(report::Message),
     Hotkey(Hotkey),
+    Loaded(store::SaveData),
 }
 
 #[derive(Debug)]
 enum Operation {
     Sale(usize, sale::Operation),
+    Report(report::Instruction),
 }
 
 struct App {
@@ -46,6 +55,8 @@ struct App {
     sales: HashMap<usize, sale::Sale>,
     pending_sale: (usize, sale::Sale),
     next_sale_id: AtomicUsize,
+    catalog: Vec<catalog::Product>,
+    tax_groups: Vec<tax::TaxGroup>,
 }
 
 impl App {
@@ -67,6 +78,7 @@ impl App {
                     sale::Mode::Edit => format!("iced • {} • Edit", sale_name),
                 }
             }
+            Screen::Report => "iced • Report".to_string(),
         }
     }
 
@@ -78,8 +90,10 @@ impl App {
                 sales: HashMap::new(),
                 pending_sale: (initial_id, Sale::default()),
                 next_sale_id: AtomicUsize::new(initial_id + 1),
+                catalog: catalog::load(),
+                tax_groups: tax::load().groups,
             },
-            Task::none(),
+            Task::perform(async { store::load() }, Message::Loaded),
         )
     }
 
@@ -91,6 +105,14 @@ impl App {
             Message::List(list::Message::SelectSale(id)) => {
                 self.screen = Screen::Sale(sale::Mode::View, id);
             }
+            Message::List(list::Message::ViewReport) => {
+                self.screen = Screen::Report;
+            }
+            Message::Loaded(data) => {
+                self.sales = data.sales;
+                self.pending_sale.0 = data.next_sale_id;
+                self.next_sale_id = AtomicUsize::new(data.next_sale_id + 1);
+            }
             Message::Hotkey(hotkey) => match self.screen {
                 Screen::List => {}
                 Screen::Sale(mode, sale_id) => {
@@ -111,6 +133,20 @@ impl App {
                         Task::none()
                     };
 
+                    return operation_task.chain(action.task);
+                }
+                Screen::Report => {
+                    // Let the report module handle the hotkey and return an Action
+                    let action = report::handle_hotkey(hotkey)
+                        .map_operation(Operation::Report)
+                        .map(Message::Report);
+
+                    let operation_task = if let Some(operation) = action.operation {
+                        self.perform(operation)
+                    } else {
+                        Task::none()
+                    };
+
                     return operation_task.chain(action.task);
                 }
             },
@@ -122,7 +158,7 @@ impl App {
                 };
 
                 // Let the sale module handle the message and return an Action
-                let action = sale::update(sale, msg)
+                let action = sale::update(sale, msg, &self.tax_groups)
                     .map_operation(move |o| Operation::Sale(sale_id, o))
                     .map(move |m| Message::Sale(sale_id, m));
 
@@ -132,6 +168,20 @@ impl App {
                     Task::none()
                 };
 
+                return operation_task.chain(action.task);
+            }
+            Message::Report(msg) => {
+                // Let the report module handle the message and return an Action
+                let action = report::update(msg)
+                    .map_operation(Operation::Report)
+                    .map(Message::Report);
+
+                let operation_task = if let Some(operation) = action.operation {
+                    self.perform(operation)
+                } else {
+                    Task::none()
+                };
+
                 return operation_task.chain(action.task);
             }
         }
@@ -147,8 +197,18 @@ impl App {
                 } else {
                     &self.sales[id]
                 };
-                sale::view(sale, *mode).map(|msg| Message::Sale(*id, msg))
+                sale::view(sale, *mode, &self.catalog, &self.tax_groups)
+                    .map(|msg| Message::Sale(*id, msg))
             }
+            Screen::Report => report::view(&report::Report::generate(&self.sales, &self.tax_groups))
+                .map(Message::Report),
+        }
+    }
+
+    fn save_data(&self) -> store::SaveData {
+        store::SaveData {
+            sales: self.sales.clone(),
+            next_sale_id: self.next_sale_id.load(Ordering::SeqCst),
         }
     }
 
@@ -156,7 +216,7 @@ impl App {
         match operation {
             Operation::Sale(sale_id, operation) => match operation {
                 sale::Operation::Back => match self.screen {
-                    Screen::List => {}
+                    Screen::List | Screen::Report => {}
                     Screen::Sale(mode, sale_id) => match mode {
                         sale::Mode::Edit => self.screen = Screen::Sale(sale::Mode::View, sale_id),
                         sale::Mode::View => self.screen = Screen::List,
@@ -180,6 +240,8 @@ impl App {
                     } else {
                         self.screen = Screen::Sale(sale::Mode::View, sale_id);
                     }
+
+                    let _ = store::save(&self.save_data());
                 }
                 sale::Operation::StartEdit => {
                     self.screen = Screen::Sale(sale::Mode::Edit, sale_id);
@@ -188,6 +250,9 @@ impl App {
                     self.screen = Screen::Sale(sale::Mode::View, sale_id);
                 }
             },
+            Operation::Report(report::Instruction::Back) => {
+                self.screen = Screen::List;
+            }
         }
 
         Task::none()
@@ -0,0 +1,255 @@
+//! A fixed-point currency type so totals never drift the way `f32` does
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+
+/// An amount of money stored as an exact count of minor units (cents).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize)]
+pub struct Money(i64);
+
+// Deserialized by hand (rather than derived) so saves written before money
+// was switched from a dollar-denominated `f32` to a cent-denominated `i64`
+// still load: a JSON float is read as legacy dollars, an integer as cents.
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MoneyVisitor;
+
+        impl<'de> Visitor<'de> for MoneyVisitor {
+            type Value = Money;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a number of cents, or a legacy decimal dollar amount")
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Money, E> {
+                Ok(Money(v))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Money, E>
+            where
+                E: de::Error,
+            {
+                i64::try_from(v)
+                    .map(Money)
+                    .map_err(|_| E::custom("money amount out of range"))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Money, E> {
+                Ok(Money((v * 100.0).round() as i64))
+            }
+        }
+
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.0
+    }
+
+    /// Parse a user-entered amount like "12.5" or "3" into cents. Returns
+    /// `None` for anything that isn't a valid amount with at most two
+    /// fraction digits.
+    pub fn parse(input: &str) -> Option<Money> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+
+        let (sign, input) = match input.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, input),
+        };
+
+        let (whole, fraction) = match input.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (input, ""),
+        };
+
+        if fraction.len() > 2 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        if !whole.is_empty() && !whole.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let whole: i64 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+        let fraction: i64 = format!("{fraction:0<2}").parse().ok()?;
+
+        Some(Money(sign * (whole * 100 + fraction)))
+    }
+
+    /// Multiply a unit price by a quantity.
+    pub fn mul_qty(&self, quantity: u32) -> Money {
+        Money(self.0 * i64::from(quantity))
+    }
+
+    /// Apply a tax rate expressed as an exact `numerator / denominator`
+    /// fraction, rounding half-to-even so per-line tax sums match the
+    /// printed total.
+    pub fn apply_rate(&self, numerator: u32, denominator: u32) -> Money {
+        if denominator == 0 {
+            return Money::ZERO;
+        }
+
+        let amount = i128::from(self.0) * i128::from(numerator);
+        let denominator = i128::from(denominator);
+
+        let quotient = amount / denominator;
+        let remainder = amount % denominator;
+
+        Money(round_half_to_even(quotient, remainder, denominator) as i64)
+    }
+}
+
+/// Parse a decimal string like "18.5" into an exact `numerator / denominator`
+/// fraction (e.g. `(185, 10)`), so a rate entered by the user can be applied
+/// via `apply_rate` instead of drifting through `f64`.
+pub fn parse_rate(input: &str) -> Option<(u32, u32)> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    let (whole, fraction) = match input.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (input, ""),
+    };
+
+    if fraction.len() > 2 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if !whole.is_empty() && !whole.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let whole: u32 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let denominator = 10u32.pow(fraction.len() as u32);
+    let fraction: u32 = if fraction.is_empty() { 0 } else { fraction.parse().ok()? };
+
+    Some((whole * denominator + fraction, denominator))
+}
+
+/// Format a `numerator / denominator` rate back into the decimal string a
+/// user would have typed, e.g. `(185, 10)` -> `"18.5"`.
+pub fn format_rate(numerator: u32, denominator: u32) -> String {
+    if denominator <= 1 {
+        return numerator.to_string();
+    }
+
+    let mut digits = 0;
+    let mut remaining = denominator;
+    while remaining > 1 {
+        remaining /= 10;
+        digits += 1;
+    }
+
+    let whole = numerator / denominator;
+    let fraction = format!("{:0width$}", numerator % denominator, width = digits);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+fn round_half_to_even(quotient: i128, remainder: i128, denominator: i128) -> i128 {
+    match (remainder.abs() * 2).cmp(&denominator) {
+        Ordering::Less => quotient,
+        Ordering::Greater => quotient + remainder.signum(),
+        Ordering::Equal if quotient % 2 == 0 => quotient,
+        Ordering::Equal => quotient + remainder.signum(),
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        Money(self.0 + rhs.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let negative = self.0 < 0;
+        let cents = self.0.unsigned_abs();
+        write!(
+            f,
+            "{}{}.{:02}",
+            if negative { "-" } else { "" },
+            cents / 100,
+            cents % 100
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_whole_and_fractional_amounts() {
+        assert_eq!(Money::parse("12"), Some(Money::from_cents(1200)));
+        assert_eq!(Money::parse("12.5"), Some(Money::from_cents(1250)));
+        assert_eq!(Money::parse("12.50"), Some(Money::from_cents(1250)));
+        assert_eq!(Money::parse("-3.25"), Some(Money::from_cents(-325)));
+    }
+
+    #[test]
+    fn parse_accepts_boundary_inputs() {
+        assert_eq!(Money::parse(".5"), Some(Money::from_cents(50)));
+        assert_eq!(Money::parse("12."), Some(Money::from_cents(1200)));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_input() {
+        assert_eq!(Money::parse(""), None);
+        assert_eq!(Money::parse("12.555"), None);
+        assert_eq!(Money::parse("abc"), None);
+    }
+
+    #[test]
+    fn apply_rate_rounds_exact_halves_to_even() {
+        // 25 * 1/2 = 12.5: the nearest even neighbor is 12, not 13.
+        assert_eq!(Money::from_cents(25).apply_rate(1, 2), Money::from_cents(12));
+        // 15 * 1/2 = 7.5: the nearest even neighbor is 8, not 7.
+        assert_eq!(Money::from_cents(15).apply_rate(1, 2), Money::from_cents(8));
+    }
+
+    #[test]
+    fn apply_rate_rounds_negative_halves_to_even() {
+        // -15 * 1/2 = -7.5: the nearest even neighbor is -8, not -7.
+        assert_eq!(
+            Money::from_cents(-15).apply_rate(1, 2),
+            Money::from_cents(-8)
+        );
+    }
+
+    #[test]
+    fn apply_rate_rounds_non_halves_normally() {
+        assert_eq!(Money::from_cents(100).apply_rate(8, 100), Money::from_cents(8));
+        assert_eq!(Money::from_cents(99).apply_rate(1, 3), Money::from_cents(33));
+    }
+}
@@ -0,0 +1,92 @@
+//! Render a `Sale` into a monospaced, column-aligned receipt
+use std::path::PathBuf;
+
+use crate::money::Money;
+use crate::sale::{Sale, SaleItem};
+use crate::tax::TaxGroup;
+
+const WIDTH: usize = 40;
+
+/// Render a sale as a printable, fixed-width receipt.
+pub fn receipt(sale: &Sale, tax_groups: &[TaxGroup]) -> String {
+    let mut out = String::new();
+
+    out.push_str(&center(if sale.name.is_empty() {
+        "Untitled sale"
+    } else {
+        &sale.name
+    }));
+    out.push_str(&"-".repeat(WIDTH));
+    out.push('\n');
+
+    for item in &sale.items {
+        out.push_str(&item_line(item));
+    }
+
+    out.push_str(&"-".repeat(WIDTH));
+    out.push('\n');
+    out.push_str(&total_line("Subtotal", sale.calculate_subtotal()));
+
+    for (group, tax) in sale.tax_by_group(tax_groups) {
+        out.push_str(&total_line(&format!("{group} tax"), tax));
+    }
+
+    out.push_str(&total_line(
+        "Service charge",
+        sale.calculate_service_charge(),
+    ));
+    out.push_str(&total_line(
+        "Gratuity",
+        sale.gratuity_amount.unwrap_or(Money::ZERO),
+    ));
+    out.push_str(&"-".repeat(WIDTH));
+    out.push('\n');
+    out.push_str(&total_line("Total", sale.calculate_total(tax_groups)));
+
+    out
+}
+
+/// Write the rendered receipt for `sale` to a text file next to the
+/// executable, returning the path it was written to.
+pub fn export(sale: &Sale, tax_groups: &[TaxGroup]) -> std::io::Result<PathBuf> {
+    let path = receipt_path(sale);
+    std::fs::write(&path, receipt(sale, tax_groups))?;
+    Ok(path)
+}
+
+fn receipt_path(sale: &Sale) -> PathBuf {
+    let slug: String = sale
+        .name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+
+    let slug = slug.trim_matches('-');
+    let file_name = if slug.is_empty() {
+        "receipt.txt".to_string()
+    } else {
+        format!("receipt-{slug}.txt")
+    };
+
+    PathBuf::from(file_name)
+}
+
+fn item_line(item: &SaleItem) -> String {
+    let line_total = item.price().mul_qty(item.quantity());
+    format!(
+        "{:<20}{:>4} x {:>7} = {:>7}\n",
+        item.name,
+        item.quantity(),
+        item.price().to_string(),
+        line_total
+    )
+}
+
+fn total_line(label: &str, amount: Money) -> String {
+    format!("{:<28}{:>12}\n", label, amount.to_string())
+}
+
+fn center(title: &str) -> String {
+    let padding = WIDTH.saturating_sub(title.len()) / 2;
+    format!("{}{}\n", " ".repeat(padding), title)
+}
@@ -0,0 +1,179 @@
+//! Aggregate every sale into an end-of-day summary
+use std::collections::HashMap;
+
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+use crate::money::Money;
+use crate::sale::Sale;
+use crate::tax::TaxGroup;
+use crate::{Action, Hotkey};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Back,
+}
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    Back,
+}
+
+pub struct Report {
+    pub total_revenue: Money,
+    pub tax_by_group: Vec<(TaxGroup, Money)>,
+    pub total_service_charge: Money,
+    pub total_gratuity: Money,
+    pub item_count: u32,
+    pub best_by_count: Vec<(String, u32, Money)>,
+    pub best_by_revenue: Vec<(String, u32, Money)>,
+}
+
+impl Report {
+    pub fn generate(sales: &HashMap<usize, Sale>, tax_groups: &[TaxGroup]) -> Self {
+        let mut tax_totals: HashMap<TaxGroup, Money> = HashMap::new();
+        let mut by_name: HashMap<String, (u32, Money)> = HashMap::new();
+
+        let mut total_revenue = Money::ZERO;
+        let mut total_service_charge = Money::ZERO;
+        let mut total_gratuity = Money::ZERO;
+        let mut item_count = 0;
+
+        for sale in sales.values() {
+            total_revenue = total_revenue + sale.calculate_subtotal();
+            total_service_charge = total_service_charge + sale.calculate_service_charge();
+            total_gratuity = total_gratuity + sale.gratuity_amount.unwrap_or(Money::ZERO);
+
+            for (group, tax) in sale.tax_by_group(tax_groups) {
+                let entry = tax_totals.entry(group).or_insert(Money::ZERO);
+                *entry = *entry + tax;
+            }
+
+            for item in &sale.items {
+                item_count += item.quantity();
+
+                let line_total = item.price().mul_qty(item.quantity());
+                let entry = by_name
+                    .entry(item.name.clone())
+                    .or_insert((0, Money::ZERO));
+                entry.0 += item.quantity();
+                entry.1 = entry.1 + line_total;
+            }
+        }
+
+        let tax_by_group = tax_groups
+            .iter()
+            .filter_map(|group| tax_totals.get(group).map(|&tax| (group.clone(), tax)))
+            .collect();
+
+        let mut best_by_count: Vec<_> = by_name
+            .iter()
+            .map(|(name, (count, revenue))| (name.clone(), *count, *revenue))
+            .collect();
+        best_by_count.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let mut best_by_revenue = best_by_count.clone();
+        best_by_revenue.sort_by(|a, b| b.2.cmp(&a.2));
+
+        Self {
+            total_revenue,
+            tax_by_group,
+            total_service_charge,
+            total_gratuity,
+            item_count,
+            best_by_count,
+            best_by_revenue,
+        }
+    }
+}
+
+pub fn update(message: Message) -> Action<Instruction, Message> {
+    match message {
+        Message::Back => Action::instruction(Instruction::Back),
+    }
+}
+
+pub fn view(report: &Report) -> Element<Message> {
+    let header = row![
+        button("Back").on_press(Message::Back),
+        text("End-of-day report").size(24),
+    ]
+    .spacing(8)
+    .align_y(iced::Alignment::Center);
+
+    let totals = column![
+        row![
+            text("Total revenue"),
+            text(report.total_revenue.to_string())
+        ]
+        .spacing(8),
+        row![
+            text("Service charges"),
+            text(report.total_service_charge.to_string())
+        ]
+        .spacing(8),
+        row![
+            text("Gratuity"),
+            text(report.total_gratuity.to_string())
+        ]
+        .spacing(8),
+        row![text("Items sold"), text(report.item_count.to_string())].spacing(8),
+    ]
+    .spacing(4);
+
+    let tax = report.tax_by_group.iter().fold(column![].spacing(4), |col, (group, tax)| {
+        col.push(row![text(format!("{group} tax")), text(tax.to_string())].spacing(8))
+    });
+
+    let by_count = report
+        .best_by_count
+        .iter()
+        .fold(column![].spacing(4), |col, (name, count, revenue)| {
+            col.push(
+                row![
+                    text(name.clone()).width(Length::FillPortion(2)),
+                    text(count.to_string()).width(Length::FillPortion(1)),
+                    text(revenue.to_string()).width(Length::FillPortion(1)),
+                ]
+                .spacing(8),
+            )
+        });
+
+    let by_revenue =
+        report
+            .best_by_revenue
+            .iter()
+            .fold(column![].spacing(4), |col, (name, count, revenue)| {
+                col.push(
+                    row![
+                        text(name.clone()).width(Length::FillPortion(2)),
+                        text(count.to_string()).width(Length::FillPortion(1)),
+                        text(revenue.to_string()).width(Length::FillPortion(1)),
+                    ]
+                    .spacing(8),
+                )
+            });
+
+    container(scrollable(
+        column![
+            header,
+            totals,
+            tax,
+            text("Best sellers by quantity").size(18),
+            by_count,
+            text("Best sellers by revenue").size(18),
+            by_revenue,
+        ]
+        .spacing(16)
+        .padding(16),
+    ))
+    .width(Length::Fill)
+    .into()
+}
+
+pub fn handle_hotkey(hotkey: Hotkey) -> Action<Instruction, Message> {
+    match hotkey {
+        Hotkey::Escape => Action::instruction(Instruction::Back),
+        Hotkey::Tab(_) => Action::none(),
+    }
+}
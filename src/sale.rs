@@ -1,9 +1,13 @@
 //! View and edit sales
 use iced::widget::{focus_next, text_input};
-use iced::Element;
+use iced::{Element, Task};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::tax::TaxGroup;
+use crate::money::{self, Money};
+use crate::tax::{self, TaxGroup};
 use crate::{Action, Hotkey};
 
 pub mod edit;
@@ -15,13 +19,27 @@ pub enum Mode {
     Edit,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaleItem {
     pub id: usize,
     pub name: String,
-    price: Option<f32>,
+    #[serde(default)]
+    price: Option<Money>,
+    #[serde(default, deserialize_with = "deserialize_quantity")]
     quantity: Option<u32>,
-    pub tax_group: TaxGroup,
+    #[serde(alias = "tax_group", default = "tax::default_tax_group_id")]
+    pub tax_group_id: String,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Accepts both the current integer quantity and the `f32` quantity that
+/// older saves stored before this field became an integer.
+fn deserialize_quantity<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<f64>::deserialize(deserializer)?.map(|v| v.round() as u32))
 }
 
 impl Default for SaleItem {
@@ -33,75 +51,109 @@ impl Default for SaleItem {
             name: String::new(),
             price: None,
             quantity: None,
-            tax_group: TaxGroup::Food,
+            tax_group_id: tax::DEFAULT_TAX_GROUP_ID.to_string(),
+            note: None,
         }
     }
 }
 
 impl SaleItem {
-    pub fn price(&self) -> f32 {
-        self.price.unwrap_or(0.0)
+    pub fn price(&self) -> Money {
+        self.price.unwrap_or(Money::ZERO)
     }
-    pub fn quantity(&self) -> f32 {
-        self.quantity.unwrap_or(0) as f32
+    pub fn quantity(&self) -> u32 {
+        self.quantity.unwrap_or(0)
     }
     pub fn price_string(&self) -> String {
-        self.price.map_or(String::new(), |p| format!("{:.2}", p))
+        self.price.map_or(String::new(), |p| p.to_string())
     }
     pub fn quantity_string(&self) -> String {
         self.quantity.map_or(String::new(), |q| q.to_string())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sale {
     pub items: Vec<SaleItem>,
-    pub service_charge_percent: Option<f32>,
-    pub gratuity_amount: Option<f32>,
+    #[serde(default)]
+    pub service_charge_rate: Option<(u32, u32)>,
+    #[serde(default)]
+    pub gratuity_amount: Option<Money>,
     pub name: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// The outcome of the most recent "Export receipt" action, rendered as
+    /// a transient status line; not persisted.
+    #[serde(skip)]
+    pub export_status: Option<Result<PathBuf, String>>,
 }
 
 impl Default for Sale {
     fn default() -> Self {
         Self {
             items: Vec::new(),
-            service_charge_percent: None,
+            service_charge_rate: None,
             gratuity_amount: None,
             name: String::new(),
+            note: None,
+            export_status: None,
         }
     }
 }
 
 impl Sale {
-    pub fn calculate_subtotal(&self) -> f32 {
+    pub fn calculate_subtotal(&self) -> Money {
         self.items
             .iter()
-            .map(|item| item.price() * item.quantity())
+            .map(|item| item.price().mul_qty(item.quantity()))
             .sum()
     }
 
-    pub fn calculate_tax(&self) -> f32 {
-        self.items
+    /// Tax owed per tax group, in `tax_groups` order, omitting groups this
+    /// sale owes nothing to. Shared by `calculate_tax` and by the receipt
+    /// and end-of-day report, which both need the same per-group breakdown.
+    pub fn tax_by_group(&self, tax_groups: &[TaxGroup]) -> Vec<(TaxGroup, Money)> {
+        let mut totals: HashMap<&str, Money> = HashMap::new();
+
+        for item in &self.items {
+            let (numerator, denominator) = tax::find(tax_groups, &item.tax_group_id)
+                .map_or((0, 1), TaxGroup::tax_rate);
+            let tax = item
+                .price()
+                .mul_qty(item.quantity())
+                .apply_rate(numerator, denominator);
+
+            let entry = totals.entry(item.tax_group_id.as_str()).or_insert(Money::ZERO);
+            *entry = *entry + tax;
+        }
+
+        tax_groups
             .iter()
-            .map(|item| {
-                item.price() * item.quantity() * item.tax_group.tax_rate()
-            })
+            .filter_map(|group| totals.get(group.id.as_str()).map(|&tax| (group.clone(), tax)))
+            .collect()
+    }
+
+    pub fn calculate_tax(&self, tax_groups: &[TaxGroup]) -> Money {
+        self.tax_by_group(tax_groups)
+            .into_iter()
+            .map(|(_, tax)| tax)
             .sum()
     }
 
-    pub fn calculate_service_charge(&self) -> f32 {
-        let subtotal = self.calculate_subtotal();
-        match self.service_charge_percent {
-            Some(percent) => subtotal * (percent / 100.0),
-            None => 0.0,
+    pub fn calculate_service_charge(&self) -> Money {
+        match self.service_charge_rate {
+            Some((numerator, denominator)) => self
+                .calculate_subtotal()
+                .apply_rate(numerator, denominator * 100),
+            None => Money::ZERO,
         }
     }
 
-    pub fn calculate_total(&self) -> f32 {
+    pub fn calculate_total(&self, tax_groups: &[TaxGroup]) -> Money {
         let subtotal = self.calculate_subtotal();
-        let tax = self.calculate_tax();
+        let tax = self.calculate_tax(tax_groups);
         let service_charge = self.calculate_service_charge();
-        let gratuity = self.gratuity_amount.unwrap_or(0.0);
+        let gratuity = self.gratuity_amount.unwrap_or(Money::ZERO);
 
         subtotal + tax + service_charge + gratuity
     }
@@ -124,6 +176,7 @@ pub enum Instruction {
 pub fn update(
     sale: &mut Sale,
     message: Message,
+    tax_groups: &[TaxGroup],
 ) -> Action<Instruction, Message> {
     match message {
         Message::Show(msg) => match msg {
@@ -132,6 +185,21 @@ pub fn update(
                 Action::instruction(Instruction::StartEdit)
                     .with_task(focus_next())
             }
+            show::Message::CopyReceipt => Action::task(iced::clipboard::write(
+                crate::render::receipt(sale, tax_groups),
+            )),
+            show::Message::ExportReceipt => {
+                let sale = sale.clone();
+                let tax_groups = tax_groups.to_vec();
+                Action::task(Task::perform(
+                    async move { crate::render::export(&sale, &tax_groups).map_err(|e| e.to_string()) },
+                    |result| Message::Show(show::Message::Exported(result)),
+                ))
+            }
+            show::Message::Exported(result) => {
+                sale.export_status = Some(result);
+                Action::none()
+            }
         },
         Message::Edit(msg) => match msg {
             edit::Message::Cancel => Action::instruction(Instruction::Cancel),
@@ -140,6 +208,10 @@ pub fn update(
                 sale.name = name;
                 Action::none()
             }
+            edit::Message::NoteInput(note) => {
+                sale.note = if note.is_empty() { None } else { Some(note) };
+                Action::none()
+            }
             edit::Message::NameSubmit => {
                 if sale.items.is_empty() {
                     sale.items.push(SaleItem::default());
@@ -162,7 +234,7 @@ pub fn update(
                             item.price = if price.is_empty() {
                                 None
                             } else {
-                                price.parse().ok()
+                                Money::parse(&price)
                             };
                         }
                         edit::Field::Quantity(qty) => {
@@ -172,7 +244,15 @@ pub fn update(
                                 qty.parse().ok()
                             };
                         }
-                        edit::Field::TaxGroup(group) => item.tax_group = group,
+                        edit::Field::TaxGroup(group_id) => item.tax_group_id = group_id,
+                        edit::Field::Product(product) => {
+                            item.name = product.name;
+                            item.price = Some(product.default_price);
+                            item.tax_group_id = product.default_tax_group_id;
+                        }
+                        edit::Field::Note(note) => {
+                            item.note = if note.is_empty() { None } else { Some(note) };
+                        }
                     }
                 }
                 Action::none()
@@ -193,6 +273,13 @@ pub fn update(
                         Action::task(text_input::focus(edit::form_id(
                             "price", id,
                         )))
+                    } else if item.note.is_none() {
+                        if let Some(item) = sale.items.iter_mut().find(|i| i.id == id) {
+                            item.note = Some(String::new());
+                        }
+                        Action::task(text_input::focus(edit::form_id(
+                            "note", id,
+                        )))
                     } else {
                         sale.items.push(SaleItem::default());
                         Action::task(text_input::focus(edit::form_id(
@@ -205,21 +292,34 @@ pub fn update(
                 }
             }
             edit::Message::UpdateServiceCharge(val) => {
-                sale.service_charge_percent = Some(val);
+                sale.service_charge_rate = if val.is_empty() {
+                    None
+                } else {
+                    money::parse_rate(&val)
+                };
                 Action::none()
             }
             edit::Message::UpdateGratuity(val) => {
-                sale.gratuity_amount = Some(val);
+                sale.gratuity_amount = if val.is_empty() {
+                    None
+                } else {
+                    Money::parse(&val)
+                };
                 Action::none()
             }
         },
     }
 }
 
-pub fn view(sale: &Sale, mode: Mode) -> Element<Message> {
+pub fn view(
+    sale: &Sale,
+    mode: Mode,
+    catalog: &[crate::catalog::Product],
+    tax_groups: &[TaxGroup],
+) -> Element<Message> {
     match mode {
-        Mode::View => show::view(sale).map(Message::Show),
-        Mode::Edit => edit::view(sale).map(Message::Edit),
+        Mode::View => show::view(sale, tax_groups).map(Message::Show),
+        Mode::Edit => edit::view(sale, catalog, tax_groups).map(Message::Edit),
     }
 }
 
@@ -3,6 +3,8 @@ use iced::keyboard::Modifiers;
 use iced::widget::{button, column, container, focus_next, focus_previous, pick_list, row, text_input};
 use iced::{Element, Length};
 
+use crate::catalog::Product;
+use crate::money;
 use crate::tax::TaxGroup;
 use crate::{Action, Hotkey};
 
@@ -15,12 +17,13 @@ pub enum Message {
     Save,
     NameInput(String),
     NameSubmit,
+    NoteInput(String),
     AddItem,
     RemoveItem(usize),
     UpdateItem(usize, Field),
     SubmitItem(usize),
-    UpdateServiceCharge(f32),
-    UpdateGratuity(f32),
+    UpdateServiceCharge(String),
+    UpdateGratuity(String),
 }
 
 #[derive(Debug, Clone)]
@@ -28,42 +31,45 @@ pub enum Field {
     Name(String),
     Price(String),
     Quantity(String),
-    TaxGroup(TaxGroup),
+    TaxGroup(String),
+    Product(Product),
+    Note(String),
 }
 
 pub fn form_id(field: &str, id: usize) -> text_input::Id {
     text_input::Id::new(format!("{field}-{id}"))
 }
 
-fn parse_or_zero(value: &str) -> f32 {
-    value.parse().unwrap_or(0.0)
-}
-
-pub fn view(sale: &Sale) -> Element<Message> {
+pub fn view(sale: &Sale, catalog: &[Product], tax_groups: &[TaxGroup]) -> Element<Message> {
     let name_input = text_input("Sale name", &sale.name)
         .on_input(Message::NameInput)
         .on_submit(Message::NameSubmit);
 
+    let note_input = text_input("Sale note", sale.note.as_deref().unwrap_or_default())
+        .on_input(Message::NoteInput);
+
     let items = sale
         .items
         .iter()
-        .fold(column![].spacing(8), |col, item| col.push(item_row(item)));
+        .fold(column![].spacing(8), |col, item| {
+            col.push(item_row(item, catalog, tax_groups))
+        });
 
     let totals = row![
         text_input(
             "Service charge %",
             &sale
-                .service_charge_percent
-                .map_or(String::new(), |v| v.to_string()),
+                .service_charge_rate
+                .map_or(String::new(), |(n, d)| money::format_rate(n, d)),
         )
-        .on_input(|v| Message::UpdateServiceCharge(parse_or_zero(&v))),
+        .on_input(Message::UpdateServiceCharge),
         text_input(
             "Gratuity",
             &sale
                 .gratuity_amount
                 .map_or(String::new(), |v| v.to_string()),
         )
-        .on_input(|v| Message::UpdateGratuity(parse_or_zero(&v))),
+        .on_input(Message::UpdateGratuity),
     ]
     .spacing(8);
 
@@ -75,7 +81,7 @@ pub fn view(sale: &Sale) -> Element<Message> {
     .spacing(8);
 
     container(
-        column![name_input, items, totals, actions]
+        column![name_input, note_input, items, totals, actions]
             .spacing(16)
             .padding(16),
     )
@@ -83,10 +89,19 @@ pub fn view(sale: &Sale) -> Element<Message> {
     .into()
 }
 
-fn item_row(item: &SaleItem) -> Element<Message> {
+fn item_row(item: &SaleItem, catalog: &[Product], tax_groups: &[TaxGroup]) -> Element<Message> {
     let id = item.id;
+    let selected_group = tax_groups
+        .iter()
+        .find(|group| group.id == item.tax_group_id)
+        .cloned();
 
     row![
+        pick_list(catalog.to_vec(), None::<Product>, move |product| {
+            Message::UpdateItem(id, Field::Product(product))
+        })
+        .placeholder("Catalog")
+        .width(Length::FillPortion(1)),
         text_input("Item", &item.name)
             .id(form_id("name", id))
             .on_input(move |v| Message::UpdateItem(id, Field::Name(v)))
@@ -102,10 +117,15 @@ fn item_row(item: &SaleItem) -> Element<Message> {
             .on_input(move |v| Message::UpdateItem(id, Field::Price(v)))
             .on_submit(Message::SubmitItem(id))
             .width(Length::FillPortion(1)),
-        pick_list(TaxGroup::ALL, Some(item.tax_group), move |group| {
-            Message::UpdateItem(id, Field::TaxGroup(group))
+        pick_list(tax_groups.to_vec(), selected_group, move |group| {
+            Message::UpdateItem(id, Field::TaxGroup(group.id))
         })
         .width(Length::FillPortion(1)),
+        text_input("Note", item.note.as_deref().unwrap_or_default())
+            .id(form_id("note", id))
+            .on_input(move |v| Message::UpdateItem(id, Field::Note(v)))
+            .on_submit(Message::SubmitItem(id))
+            .width(Length::FillPortion(2)),
         button("x").on_press(Message::RemoveItem(id)),
     ]
     .spacing(8)
@@ -1,7 +1,11 @@
 //! Read-only view of a sale
+use std::path::PathBuf;
+
 use iced::widget::{button, column, container, row, text};
 use iced::{Element, Length};
 
+use crate::money::Money;
+use crate::tax::TaxGroup;
 use crate::{Action, Hotkey};
 
 use super::Instruction;
@@ -11,9 +15,12 @@ use super::Sale;
 pub enum Message {
     Back,
     StartEdit,
+    CopyReceipt,
+    ExportReceipt,
+    Exported(Result<PathBuf, String>),
 }
 
-pub fn view(sale: &Sale) -> Element<Message> {
+pub fn view(sale: &Sale, tax_groups: &[TaxGroup]) -> Element<Message> {
     let header = row![
         button("Back").on_press(Message::Back),
         text(if sale.name.is_empty() {
@@ -23,44 +30,56 @@ pub fn view(sale: &Sale) -> Element<Message> {
         })
         .size(24),
         button("Edit").on_press(Message::StartEdit),
+        button("Copy receipt").on_press(Message::CopyReceipt),
+        button("Export receipt").on_press(Message::ExportReceipt),
     ]
     .spacing(8)
     .align_y(iced::Alignment::Center);
 
+    let note = sale.note.as_deref().map(text);
+
+    let export_status = sale.export_status.as_ref().map(|result| match result {
+        Ok(path) => text(format!("Exported receipt to {}", path.display())),
+        Err(error) => text(format!("Failed to export receipt: {error}")),
+    });
+
     let items = sale.items.iter().fold(column![].spacing(4), |col, item| {
         col.push(row![
             text(item.name.clone()).width(Length::FillPortion(3)),
             text(item.quantity_string()).width(Length::FillPortion(1)),
             text(item.price_string()).width(Length::FillPortion(1)),
+            text(item.note.clone().unwrap_or_default()).width(Length::FillPortion(2)),
         ])
     });
 
     let totals = column![
-        row![
-            text("Subtotal"),
-            text(format!("{:.2}", sale.calculate_subtotal()))
-        ]
-        .spacing(8),
-        row![text("Tax"), text(format!("{:.2}", sale.calculate_tax()))].spacing(8),
+        row![text("Subtotal"), text(sale.calculate_subtotal().to_string())].spacing(8),
+        row![text("Tax"), text(sale.calculate_tax(tax_groups).to_string())].spacing(8),
         row![
             text("Service charge"),
-            text(format!("{:.2}", sale.calculate_service_charge()))
+            text(sale.calculate_service_charge().to_string())
         ]
         .spacing(8),
         row![
             text("Gratuity"),
-            text(format!("{:.2}", sale.gratuity_amount.unwrap_or(0.0)))
-        ]
-        .spacing(8),
-        row![
-            text("Total"),
-            text(format!("{:.2}", sale.calculate_total()))
+            text(sale.gratuity_amount.unwrap_or(Money::ZERO).to_string())
         ]
         .spacing(8),
+        row![text("Total"), text(sale.calculate_total(tax_groups).to_string())].spacing(8),
     ]
     .spacing(4);
 
-    container(column![header, items, totals].spacing(16).padding(16))
+    let body = column![header].spacing(16);
+    let body = match note {
+        Some(note) => body.push(note),
+        None => body,
+    };
+    let body = match export_status {
+        Some(status) => body.push(status),
+        None => body,
+    };
+
+    container(body.push(items).push(totals).padding(16))
         .width(Length::Fill)
         .into()
 }
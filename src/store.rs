@@ -0,0 +1,53 @@
+//! Persist sales to disk so they survive restarts
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::sale::Sale;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    pub sales: HashMap<usize, Sale>,
+    pub next_sale_id: usize,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            sales: HashMap::new(),
+            next_sale_id: 0,
+        }
+    }
+}
+
+fn save_path() -> PathBuf {
+    PathBuf::from("sales.json")
+}
+
+/// Load previously saved sales, falling back to an empty save if the file
+/// is missing. A file that exists but fails to parse is reported to stderr
+/// rather than silently discarded, since that's otherwise indistinguishable
+/// from a fresh install and the next save would overwrite it for good.
+pub fn load() -> SaveData {
+    let Ok(contents) = std::fs::read_to_string(save_path()) else {
+        return SaveData::default();
+    };
+
+    match serde_json::from_str(&contents) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!(
+                "Failed to parse {}, starting with no saved sales: {err}",
+                save_path().display()
+            );
+            SaveData::default()
+        }
+    }
+}
+
+pub fn save(data: &SaveData) -> std::io::Result<()> {
+    let contents = serde_json::to_string_pretty(data)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+
+    std::fs::write(save_path(), contents)
+}
@@ -1,30 +1,84 @@
-//! Tax categories and their rates
+//! Tax groups are data-driven so rates can be adjusted without recompiling
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TaxGroup {
-    Food,
-    Alcohol,
-    Exempt,
+/// The tax group a new, unconfigured sale item falls back to.
+pub const DEFAULT_TAX_GROUP_ID: &str = "food";
+
+/// Used as a `#[serde(default = "...")]` target for `tax_group_id`, since
+/// that attribute needs a function rather than a constant.
+pub fn default_tax_group_id() -> String {
+    DEFAULT_TAX_GROUP_ID.to_string()
 }
 
-impl TaxGroup {
-    pub const ALL: [TaxGroup; 3] = [TaxGroup::Food, TaxGroup::Alcohol, TaxGroup::Exempt];
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TaxGroup {
+    pub id: String,
+    pub name: String,
+    pub rate_numerator: u32,
+    pub rate_denominator: u32,
+}
 
-    pub fn tax_rate(&self) -> f32 {
-        match self {
-            TaxGroup::Food => 0.08,
-            TaxGroup::Alcohol => 0.12,
-            TaxGroup::Exempt => 0.0,
-        }
+impl TaxGroup {
+    /// The tax rate as an exact `numerator / denominator` fraction, so
+    /// downstream `Money` math never has to round a float rate.
+    pub fn tax_rate(&self) -> (u32, u32) {
+        (self.rate_numerator, self.rate_denominator)
     }
 }
 
 impl std::fmt::Display for TaxGroup {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TaxGroup::Food => write!(f, "Food"),
-            TaxGroup::Alcohol => write!(f, "Alcohol"),
-            TaxGroup::Exempt => write!(f, "Exempt"),
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub groups: Vec<TaxGroup>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            groups: vec![
+                TaxGroup {
+                    id: "food".to_string(),
+                    name: "Food".to_string(),
+                    rate_numerator: 8,
+                    rate_denominator: 100,
+                },
+                TaxGroup {
+                    id: "alcohol".to_string(),
+                    name: "Alcohol".to_string(),
+                    rate_numerator: 12,
+                    rate_denominator: 100,
+                },
+                TaxGroup {
+                    id: "exempt".to_string(),
+                    name: "Exempt".to_string(),
+                    rate_numerator: 0,
+                    rate_denominator: 1,
+                },
+            ],
         }
     }
 }
+
+fn config_path() -> PathBuf {
+    PathBuf::from("tax_groups.json")
+}
+
+/// Load the tax group configuration, falling back to a sensible default
+/// (matching the rates the app used to ship hardcoded) if the file is
+/// missing or can't be parsed.
+pub fn load() -> Config {
+    std::fs::read_to_string(config_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn find<'a>(groups: &'a [TaxGroup], id: &str) -> Option<&'a TaxGroup> {
+    groups.iter().find(|group| group.id == id)
+}